@@ -13,6 +13,7 @@ fn bench_process_posts(c: &mut Criterion) {
         .map(|i| {
             let id = format!("ID{:09}", i);
             Post {
+                id: i as i64,
                 post_number: i as i64,
                 cooked: format!("<a href=\"https://youtu.be/{id}\">v</a>"),
                 username: format!("user{i}"),
@@ -34,17 +35,19 @@ fn bench_run_with_mock(c: &mut Criterion) {
 
     // Sample topic JSON served by the mock server
     let topic_json = serde_json::json!({
-        "post_stream": {"posts": [{
-            "post_number": 1,
-            "cooked": "<a href=\"https://youtu.be/BBBBBBBBBBB\">v</a>",
-            "username": "alice"
-        }]}
+        "post_stream": {
+            "posts": [{
+                "id": 1,
+                "post_number": 1,
+                "cooked": "<a href=\"https://youtu.be/BBBBBBBBBBB\">v</a>",
+                "username": "alice"
+            }],
+            "stream": [1]
+        }
     });
 
     server.mock(|when, then| {
-        when.method(httpmock::Method::GET)
-            .path("/topic.json")
-            .query_param("print", "true");
+        when.method(httpmock::Method::GET).path("/topic.json");
         then.status(200)
             .header("content-type", "application/json")
             .json_body_obj(&topic_json);
@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
 use futures::stream::{self, StreamExt};
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
 use regex::Regex;
 use reqwest::{Client, StatusCode};
 use scraper::{Html, Selector};
@@ -8,7 +10,7 @@ use serde_json;
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::LazyLock;
+use std::sync::{LazyLock, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::fs as tokio_fs;
 use tokio::time::sleep;
@@ -16,13 +18,6 @@ use url::Url;
 use zcash_address::unified::{self, Container, Encoding};
 use zcash_protocol::consensus::NetworkType;
 
-static CLIENT: LazyLock<Client> = LazyLock::new(|| {
-    Client::builder()
-        .user_agent("zcash-radio-aphelionz/0.1 (+https://github.com/aphelionz)")
-        .build()
-        .expect("Failed to build HTTP client")
-});
-
 static A_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("a").unwrap());
 
 pub static CURATION_DENYLIST: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
@@ -44,10 +39,148 @@ static UA_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?i)u1[0-9a-z]{10,}").expect("invalid UA regex"));
 
 const CACHE_DIR: &str = "./target/profile_cache";
+const VIDEO_CACHE_DIR: &str = "./target/video_cache";
 const CACHE_TTL_SECS: u64 = 24 * 60 * 60;
 const PROFILE_CONCURRENCY: usize = 3;
-const RETRY_ATTEMPTS: usize = 3;
-const RETRY_BASE_DELAY_MS: u64 = 500;
+const DEFAULT_RETRY_ATTEMPTS: usize = 3;
+const DEFAULT_RETRY_BASE_DELAY_MS: u64 = 500;
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+const INNERTUBE_PLAYER_URL: &str = "https://www.youtube.com/youtubei/v1/player";
+const INNERTUBE_CLIENT_VERSION: &str = "19.09.37";
+
+const POST_BATCH_SIZE: usize = 20;
+
+/// A reasonable default set of public Invidious instances used as a fallback
+/// when YouTube's InnerTube API soft-blocks or rate-limits us. Callers may
+/// substitute their own list via [`ScanConfigBuilder::invidious_instances`];
+/// [`ScanConfig::default`] falls back to this default set.
+pub const DEFAULT_INVIDIOUS_INSTANCES: &[&str] = &[
+    "https://invidious.nerdvpn.de",
+    "https://inv.nadeko.net",
+    "https://yewtu.be",
+    "https://invidious.jing.rocks",
+];
+
+const INVIDIOUS_INSTANCE_CACHE_TTL_SECS: u64 = 5 * 60;
+
+static INVIDIOUS_LAST_GOOD: LazyLock<Mutex<Option<(String, u64)>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+/// Runtime configuration for a scan: the HTTP client (timeouts, TLS backend),
+/// retry policy, and whether non-[`Availability::Available`] videos are kept.
+/// Build one with [`ScanConfig::builder`].
+#[derive(Clone)]
+pub struct ScanConfig {
+    client: Client,
+    retry_attempts: usize,
+    retry_base_delay_ms: u64,
+    retain_unavailable: bool,
+    invidious_instances: Vec<String>,
+}
+
+impl ScanConfig {
+    pub fn builder() -> ScanConfigBuilder {
+        ScanConfigBuilder::default()
+    }
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        ScanConfigBuilder::default()
+            .build()
+            .expect("default ScanConfig must build")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScanConfigBuilder {
+    request_timeout: Duration,
+    connect_timeout: Duration,
+    retry_attempts: usize,
+    retry_base_delay_ms: u64,
+    retain_unavailable: bool,
+    invidious_instances: Vec<String>,
+}
+
+impl Default for ScanConfigBuilder {
+    fn default() -> Self {
+        ScanConfigBuilder {
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            retry_attempts: DEFAULT_RETRY_ATTEMPTS,
+            retry_base_delay_ms: DEFAULT_RETRY_BASE_DELAY_MS,
+            retain_unavailable: true,
+            invidious_instances: DEFAULT_INVIDIOUS_INSTANCES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl ScanConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    pub fn retry_attempts(mut self, attempts: usize) -> Self {
+        self.retry_attempts = attempts;
+        self
+    }
+
+    pub fn retry_base_delay_ms(mut self, delay_ms: u64) -> Self {
+        self.retry_base_delay_ms = delay_ms;
+        self
+    }
+
+    pub fn retain_unavailable(mut self, retain: bool) -> Self {
+        self.retain_unavailable = retain;
+        self
+    }
+
+    /// Overrides the Invidious instances tried as a fallback when YouTube's
+    /// InnerTube API fails or reports a non-available status. Defaults to
+    /// [`DEFAULT_INVIDIOUS_INSTANCES`].
+    pub fn invidious_instances(mut self, instances: Vec<String>) -> Self {
+        self.invidious_instances = instances;
+        self
+    }
+
+    pub fn build(self) -> Result<ScanConfig> {
+        let builder = Client::builder()
+            .user_agent("zcash-radio-aphelionz/0.1 (+https://github.com/aphelionz)")
+            .timeout(self.request_timeout)
+            .connect_timeout(self.connect_timeout);
+
+        // The `rustls-tls-*` features select rustls for environments without
+        // a system OpenSSL (the crate's default features enable
+        // `rustls-tls-webpki-roots`); `default-tls` selects native-tls and is
+        // used only when no `rustls-tls-*` feature is enabled.
+        #[cfg(any(feature = "rustls-tls-webpki-roots", feature = "rustls-tls-native-roots"))]
+        let builder = builder.use_rustls_tls();
+
+        let client = builder.build().context("failed to build HTTP client")?;
+        Ok(ScanConfig {
+            client,
+            retry_attempts: self.retry_attempts,
+            retry_base_delay_ms: self.retry_base_delay_ms,
+            retain_unavailable: self.retain_unavailable,
+            invidious_instances: self.invidious_instances,
+        })
+    }
+}
 
 #[derive(Debug, Deserialize)]
 pub struct Topic {
@@ -57,26 +190,55 @@ pub struct Topic {
 #[derive(Debug, Deserialize)]
 pub struct PostStream {
     pub posts: Vec<Post>,
+    #[serde(default)]
+    pub stream: Vec<i64>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Post {
+    pub id: i64,
     pub post_number: i64,
     pub cooked: String,
     #[serde(default)]
     pub username: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 pub struct VideoEntry {
     pub video_id: String,
     pub source_post_url: String,
     #[serde(default)]
+    pub post_number: i64,
+    #[serde(default)]
     pub username: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tip_unified_address: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tip_has_transparent: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability: Option<Availability>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_start: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Availability {
+    Available,
+    Unavailable,
+    Private,
+    AgeRestricted,
+    Upcoming,
 }
 
 #[derive(Debug, Clone)]
@@ -85,6 +247,17 @@ struct TipInfo {
     has_transparent: bool,
 }
 
+#[derive(Debug, Clone, Default)]
+struct VideoMetadata {
+    title: Option<String>,
+    channel_name: Option<String>,
+    channel_id: Option<String>,
+    duration_secs: Option<u64>,
+    thumbnail_url: Option<String>,
+    availability: Option<Availability>,
+    scheduled_start: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct CachedTipEntry {
     cached_at: u64,
@@ -94,6 +267,25 @@ struct CachedTipEntry {
     tip_has_transparent: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct CachedVideoEntry {
+    cached_at: u64,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    channel_name: Option<String>,
+    #[serde(default)]
+    channel_id: Option<String>,
+    #[serde(default)]
+    duration_secs: Option<u64>,
+    #[serde(default)]
+    thumbnail_url: Option<String>,
+    #[serde(default)]
+    availability: Option<Availability>,
+    #[serde(default)]
+    scheduled_start: Option<u64>,
+}
+
 fn cache_path(username: &str) -> PathBuf {
     let mut sanitized = String::with_capacity(username.len());
     for ch in username.chars() {
@@ -106,6 +298,10 @@ fn cache_path(username: &str) -> PathBuf {
     Path::new(CACHE_DIR).join(format!("{}.json", sanitized))
 }
 
+fn video_cache_path(video_id: &str) -> PathBuf {
+    Path::new(VIDEO_CACHE_DIR).join(format!("{}.json", video_id))
+}
+
 fn now_timestamp() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -113,15 +309,15 @@ fn now_timestamp() -> u64 {
         .as_secs()
 }
 
-fn cache_entry_fresh(entry: &CachedTipEntry) -> bool {
-    now_timestamp().saturating_sub(entry.cached_at) <= CACHE_TTL_SECS
+fn cache_entry_fresh(cached_at: u64) -> bool {
+    now_timestamp().saturating_sub(cached_at) <= CACHE_TTL_SECS
 }
 
 async fn load_cached_tip(username: &str) -> Option<CachedTipEntry> {
     let path = cache_path(username);
     let data = tokio_fs::read(path).await.ok()?;
     let entry: CachedTipEntry = serde_json::from_slice(&data).ok()?;
-    if cache_entry_fresh(&entry) {
+    if cache_entry_fresh(entry.cached_at) {
         Some(entry)
     } else {
         None
@@ -148,6 +344,37 @@ async fn store_cached_tip(username: &str, entry: &CachedTipEntry) {
     }
 }
 
+async fn load_cached_video(video_id: &str) -> Option<CachedVideoEntry> {
+    let path = video_cache_path(video_id);
+    let data = tokio_fs::read(path).await.ok()?;
+    let entry: CachedVideoEntry = serde_json::from_slice(&data).ok()?;
+    if cache_entry_fresh(entry.cached_at) {
+        Some(entry)
+    } else {
+        None
+    }
+}
+
+async fn store_cached_video(video_id: &str, entry: &CachedVideoEntry) {
+    let path = video_cache_path(video_id);
+    if let Some(parent) = path.parent() {
+        if let Err(err) = tokio_fs::create_dir_all(parent).await {
+            eprintln!("cache: failed to create directory: {}", err);
+            return;
+        }
+    }
+    match serde_json::to_vec(entry) {
+        Ok(buf) => {
+            if let Err(err) = tokio_fs::write(&path, buf).await {
+                eprintln!("cache: failed to write entry: {}", err);
+            }
+        }
+        Err(err) => {
+            eprintln!("cache: failed to serialize entry: {}", err);
+        }
+    }
+}
+
 fn extract_unified_address(text: &str) -> Option<String> {
     UA_REGEX.find(text).map(|m| m.as_str().to_lowercase())
 }
@@ -194,7 +421,7 @@ fn find_address_in_json(value: &serde_json::Value) -> Option<String> {
     }
 }
 
-async fn fetch_tip_info_with_cache(base_url: &Url, username: &str) -> Option<TipInfo> {
+async fn fetch_tip_info_with_cache(cfg: &ScanConfig, base_url: &Url, username: &str) -> Option<TipInfo> {
     if username.is_empty() {
         return None;
     }
@@ -205,7 +432,7 @@ async fn fetch_tip_info_with_cache(base_url: &Url, username: &str) -> Option<Tip
         });
     }
 
-    match fetch_tip_info_remote(base_url, username).await {
+    match fetch_tip_info_remote(cfg, base_url, username).await {
         Ok(Some(tip)) => {
             let entry = CachedTipEntry {
                 cached_at: now_timestamp(),
@@ -231,18 +458,26 @@ async fn fetch_tip_info_with_cache(base_url: &Url, username: &str) -> Option<Tip
     }
 }
 
-async fn fetch_tip_info_remote(base_url: &Url, username: &str) -> Result<Option<TipInfo>> {
-    if let Some(info) = fetch_tip_from_json(base_url, username).await? {
+async fn fetch_tip_info_remote(
+    cfg: &ScanConfig,
+    base_url: &Url,
+    username: &str,
+) -> Result<Option<TipInfo>> {
+    if let Some(info) = fetch_tip_from_json(cfg, base_url, username).await? {
         return Ok(Some(info));
     }
-    fetch_tip_from_html(base_url, username).await
+    fetch_tip_from_html(cfg, base_url, username).await
 }
 
-async fn fetch_tip_from_json(base_url: &Url, username: &str) -> Result<Option<TipInfo>> {
+async fn fetch_tip_from_json(
+    cfg: &ScanConfig,
+    base_url: &Url,
+    username: &str,
+) -> Result<Option<TipInfo>> {
     let url = base_url
         .join(&format!("/u/{}.json", username))
         .context("building profile JSON url")?;
-    let resp = get_with_retries(&url).await?;
+    let resp = get_with_retries(cfg, &url).await?;
     if resp.status() == StatusCode::NOT_FOUND {
         return Ok(None);
     }
@@ -258,11 +493,15 @@ async fn fetch_tip_from_json(base_url: &Url, username: &str) -> Result<Option<Ti
     Ok(None)
 }
 
-async fn fetch_tip_from_html(base_url: &Url, username: &str) -> Result<Option<TipInfo>> {
+async fn fetch_tip_from_html(
+    cfg: &ScanConfig,
+    base_url: &Url,
+    username: &str,
+) -> Result<Option<TipInfo>> {
     let url = base_url
         .join(&format!("/u/{}", username))
         .context("building profile HTML url")?;
-    let resp = get_with_retries(&url).await?;
+    let resp = get_with_retries(cfg, &url).await?;
     if resp.status() == StatusCode::NOT_FOUND {
         return Ok(None);
     }
@@ -278,13 +517,313 @@ async fn fetch_tip_from_html(base_url: &Url, username: &str) -> Result<Option<Ti
     Ok(None)
 }
 
-async fn get_with_retries(url: &Url) -> Result<reqwest::Response> {
+async fn fetch_video_metadata_with_cache(cfg: &ScanConfig, video_id: &str) -> Option<VideoMetadata> {
+    if let Some(entry) = load_cached_video(video_id).await {
+        return Some(VideoMetadata {
+            title: entry.title,
+            channel_name: entry.channel_name,
+            channel_id: entry.channel_id,
+            duration_secs: entry.duration_secs,
+            thumbnail_url: entry.thumbnail_url,
+            availability: entry.availability,
+            scheduled_start: entry.scheduled_start,
+        });
+    }
+
+    let metadata = match fetch_video_metadata_remote(cfg, video_id).await {
+        Ok(Some(metadata)) if metadata.availability == Some(Availability::Available) => {
+            Some(metadata)
+        }
+        Ok(Some(metadata)) => {
+            eprintln!(
+                "metadata: {} not OK via InnerTube ({:?}), supplementing via Invidious",
+                video_id, metadata.availability
+            );
+            let supplement = fetch_video_metadata_invidious(cfg, video_id).await;
+            Some(merge_invidious_supplement(metadata, supplement))
+        }
+        Ok(None) => None,
+        Err(err) => {
+            eprintln!("metadata: failed to fetch video {}: {}", video_id, err);
+            fetch_video_metadata_invidious(cfg, video_id).await
+        }
+    };
+
+    if let Some(metadata) = &metadata {
+        let entry = CachedVideoEntry {
+            cached_at: now_timestamp(),
+            title: metadata.title.clone(),
+            channel_name: metadata.channel_name.clone(),
+            channel_id: metadata.channel_id.clone(),
+            duration_secs: metadata.duration_secs,
+            thumbnail_url: metadata.thumbnail_url.clone(),
+            availability: metadata.availability,
+            scheduled_start: metadata.scheduled_start,
+        };
+        store_cached_video(video_id, &entry).await;
+    }
+    metadata
+}
+
+/// Fills in descriptive fields InnerTube left blank (title/channel/duration/
+/// thumbnail) from an Invidious reply. Deliberately leaves `availability` and
+/// `scheduled_start` untouched — InnerTube's playability verdict is the real
+/// one, and an Invidious mirror will happily keep serving private, removed,
+/// or upcoming videos long after YouTube itself stops.
+fn merge_invidious_supplement(
+    base: VideoMetadata,
+    supplement: Option<VideoMetadata>,
+) -> VideoMetadata {
+    let Some(supplement) = supplement else {
+        return base;
+    };
+    VideoMetadata {
+        title: base.title.or(supplement.title),
+        channel_name: base.channel_name.or(supplement.channel_name),
+        channel_id: base.channel_id.or(supplement.channel_id),
+        duration_secs: base.duration_secs.or(supplement.duration_secs),
+        thumbnail_url: base.thumbnail_url.or(supplement.thumbnail_url),
+        ..base
+    }
+}
+
+async fn fetch_video_metadata_invidious(cfg: &ScanConfig, video_id: &str) -> Option<VideoMetadata> {
+    for instance in invidious_instance_order(&cfg.invidious_instances) {
+        match fetch_video_metadata_invidious_instance(cfg, &instance, video_id).await {
+            Ok(Some(metadata)) => {
+                remember_invidious_instance(&instance);
+                return Some(metadata);
+            }
+            Ok(None) => continue,
+            Err(err) => {
+                eprintln!("invidious: {} failed for {}: {}", instance, video_id, err);
+                continue;
+            }
+        }
+    }
+    None
+}
+
+fn invidious_instance_order(instances: &[String]) -> Vec<String> {
+    let mut order: Vec<String> = instances.to_vec();
+    order.shuffle(&mut thread_rng());
+
+    if let Some((last_good, cached_at)) = INVIDIOUS_LAST_GOOD.lock().unwrap().clone() {
+        if now_timestamp().saturating_sub(cached_at) <= INVIDIOUS_INSTANCE_CACHE_TTL_SECS {
+            if let Some(pos) = order.iter().position(|i| *i == last_good) {
+                let preferred = order.remove(pos);
+                order.insert(0, preferred);
+            }
+        }
+    }
+    order
+}
+
+fn remember_invidious_instance(instance: &str) {
+    *INVIDIOUS_LAST_GOOD.lock().unwrap() = Some((instance.to_string(), now_timestamp()));
+}
+
+async fn fetch_video_metadata_invidious_instance(
+    cfg: &ScanConfig,
+    instance: &str,
+    video_id: &str,
+) -> Result<Option<VideoMetadata>> {
+    let url_str = format!(
+        "{}/api/v1/videos/{}",
+        instance.trim_end_matches('/'),
+        video_id
+    );
+    let url = Url::parse(&url_str).context("building invidious url")?;
+    let resp = get_with_retries(cfg, &url).await?;
+    if resp.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !resp.status().is_success() {
+        anyhow::bail!("invidious request returned status {}", resp.status());
+    }
+    let value: serde_json::Value = resp.json().await?;
+    Ok(parse_invidious_response(&value))
+}
+
+fn parse_invidious_response(value: &serde_json::Value) -> Option<VideoMetadata> {
+    let title = value
+        .get("title")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let channel_name = value
+        .get("author")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let channel_id = value
+        .get("authorId")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let duration_secs = value.get("lengthSeconds").and_then(|v| v.as_u64());
+    let thumbnail_url = value
+        .get("videoThumbnails")
+        .and_then(|v| v.as_array())
+        .and_then(|thumbs| {
+            thumbs
+                .iter()
+                .max_by_key(|t| t.get("width").and_then(|w| w.as_u64()).unwrap_or(0))
+        })
+        .and_then(|t| t.get("url"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    Some(VideoMetadata {
+        title,
+        channel_name,
+        channel_id,
+        duration_secs,
+        thumbnail_url,
+        availability: Some(Availability::Available),
+        scheduled_start: None,
+    })
+}
+
+async fn fetch_video_metadata_remote(
+    cfg: &ScanConfig,
+    video_id: &str,
+) -> Result<Option<VideoMetadata>> {
+    let body = innertube_player_request_body(video_id);
+    let resp = post_with_retries(cfg, INNERTUBE_PLAYER_URL, &body).await?;
+    if !resp.status().is_success() {
+        anyhow::bail!("innertube player request returned status {}", resp.status());
+    }
+    let value: serde_json::Value = resp.json().await?;
+    Ok(parse_player_response(&value))
+}
+
+fn innertube_player_request_body(video_id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "context": {
+            "client": {
+                "clientName": "ANDROID",
+                "clientVersion": INNERTUBE_CLIENT_VERSION,
+                "hl": "en",
+                "gl": "US",
+            }
+        },
+        "videoId": video_id,
+    })
+}
+
+fn parse_player_response(value: &serde_json::Value) -> Option<VideoMetadata> {
+    // `playabilityStatus` is present on every InnerTube response, including
+    // deleted/ERROR videos that carry no `videoDetails` at all — read it
+    // unconditionally so those videos still get tagged instead of silently
+    // falling through with `availability: None`.
+    let availability = value
+        .get("playabilityStatus")
+        .map(parse_availability)
+        .unwrap_or(Availability::Unavailable);
+    let scheduled_start = if availability == Availability::Upcoming {
+        find_scheduled_start(value)
+    } else {
+        None
+    };
+
+    let details = value.get("videoDetails");
+    let title = details
+        .and_then(|d| d.get("title"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let channel_name = details
+        .and_then(|d| d.get("author"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let channel_id = details
+        .and_then(|d| d.get("channelId"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let duration_secs = details
+        .and_then(|d| d.get("lengthSeconds"))
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<u64>().ok());
+    let thumbnail_url = details
+        .and_then(|d| d.get("thumbnail"))
+        .and_then(|t| t.get("thumbnails"))
+        .and_then(|t| t.as_array())
+        .and_then(|thumbs| {
+            thumbs.iter().max_by_key(|t| {
+                t.get("width").and_then(|w| w.as_u64()).unwrap_or(0)
+            })
+        })
+        .and_then(|t| t.get("url"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    Some(VideoMetadata {
+        title,
+        channel_name,
+        channel_id,
+        duration_secs,
+        thumbnail_url,
+        availability: Some(availability),
+        scheduled_start,
+    })
+}
+
+fn parse_availability(status: &serde_json::Value) -> Availability {
+    let status_str = status.get("status").and_then(|v| v.as_str()).unwrap_or("");
+    let reason = status
+        .get("reason")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match status_str {
+        "OK" => Availability::Available,
+        "LOGIN_REQUIRED" => {
+            if reason.contains("age") {
+                Availability::AgeRestricted
+            } else {
+                Availability::Private
+            }
+        }
+        "LIVE_STREAM_OFFLINE" => Availability::Upcoming,
+        _ => Availability::Unavailable,
+    }
+}
+
+fn find_scheduled_start(value: &serde_json::Value) -> Option<u64> {
+    if let Some(start) = value
+        .pointer("/playabilityStatus/liveStreamability/liveStreamabilityRenderer")
+        .and_then(find_scheduled_start_key)
+    {
+        return Some(start);
+    }
+    find_scheduled_start_key(value)
+}
+
+fn find_scheduled_start_key(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(v) = map
+                .get("scheduledStartTime")
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse::<u64>().ok())
+            {
+                return Some(v);
+            }
+            map.values().find_map(find_scheduled_start_key)
+        }
+        serde_json::Value::Array(values) => values.iter().find_map(find_scheduled_start_key),
+        _ => None,
+    }
+}
+
+async fn post_with_retries(
+    cfg: &ScanConfig,
+    url: &str,
+    body: &serde_json::Value,
+) -> Result<reqwest::Response> {
     let mut attempt = 0usize;
     loop {
-        match CLIENT.get(url.clone()).send().await {
+        match cfg.client.post(url).json(body).send().await {
             Ok(resp) => {
-                if should_retry_status(resp.status()) && attempt + 1 < RETRY_ATTEMPTS {
-                    let delay = retry_delay(attempt);
+                if should_retry_status(resp.status()) && attempt + 1 < cfg.retry_attempts {
+                    let delay = retry_delay(cfg, attempt);
                     sleep(delay).await;
                     attempt += 1;
                     continue;
@@ -292,10 +831,35 @@ async fn get_with_retries(url: &Url) -> Result<reqwest::Response> {
                 return Ok(resp);
             }
             Err(err) => {
-                if attempt + 1 >= RETRY_ATTEMPTS {
+                if attempt + 1 >= cfg.retry_attempts {
                     return Err(err.into());
                 }
-                let delay = retry_delay(attempt);
+                let delay = retry_delay(cfg, attempt);
+                sleep(delay).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+async fn get_with_retries(cfg: &ScanConfig, url: &Url) -> Result<reqwest::Response> {
+    let mut attempt = 0usize;
+    loop {
+        match cfg.client.get(url.clone()).send().await {
+            Ok(resp) => {
+                if should_retry_status(resp.status()) && attempt + 1 < cfg.retry_attempts {
+                    let delay = retry_delay(cfg, attempt);
+                    sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
+                return Ok(resp);
+            }
+            Err(err) => {
+                if attempt + 1 >= cfg.retry_attempts {
+                    return Err(err.into());
+                }
+                let delay = retry_delay(cfg, attempt);
                 sleep(delay).await;
                 attempt += 1;
             }
@@ -307,9 +871,16 @@ fn should_retry_status(status: StatusCode) -> bool {
     status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
 }
 
-fn retry_delay(attempt: usize) -> Duration {
-    let base = Duration::from_millis(RETRY_BASE_DELAY_MS);
-    base * (1u32 << attempt.min(5))
+/// Full-jitter backoff: a random duration in `[0, base * 2^attempt)` (the
+/// exponent is capped at 5), which spreads out retries instead of having every
+/// caller wake at the same instant.
+fn retry_delay(cfg: &ScanConfig, attempt: usize) -> Duration {
+    let base_ms = cfg.retry_base_delay_ms * (1u64 << attempt.min(5));
+    if base_ms == 0 {
+        return Duration::ZERO;
+    }
+    let jittered_ms = thread_rng().gen_range(0..base_ms);
+    Duration::from_millis(jittered_ms)
 }
 
 pub fn is_valid_youtube_id(id: &str) -> bool {
@@ -366,6 +937,84 @@ pub fn extract_video_id(href: &str) -> Option<String> {
     None
 }
 
+async fn fetch_all_posts(cfg: &ScanConfig, topic_url: &str) -> Result<Vec<Post>> {
+    let url = format!("{}.json", topic_url);
+    let resp = get_with_retries(cfg, &Url::parse(&url).context("invalid topic url")?).await?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        eprintln!("DISCOURSE ERROR {} -> {}\n{}", url, status, body);
+        anyhow::bail!("GET {}", url);
+    }
+    let topic: Topic = resp.json().await?;
+    let mut posts = topic.post_stream.posts;
+
+    let have: HashSet<i64> = posts.iter().map(|p| p.id).collect();
+    let missing: Vec<i64> = topic
+        .post_stream
+        .stream
+        .into_iter()
+        .filter(|id| !have.contains(id))
+        .collect();
+
+    if missing.is_empty() {
+        posts.sort_by_key(|p| p.post_number);
+        return Ok(posts);
+    }
+
+    let batches: Vec<Vec<i64>> = missing.chunks(POST_BATCH_SIZE).map(<[i64]>::to_vec).collect();
+    let batch_count = batches.len();
+    let fetched = stream::iter(batches.into_iter().map(|batch| async move {
+        fetch_post_batch(cfg, topic_url, &batch).await
+    }))
+    .buffer_unordered(PROFILE_CONCURRENCY)
+    .collect::<Vec<_>>()
+    .await;
+
+    let mut failed = 0usize;
+    for result in fetched {
+        match result {
+            Ok(mut batch_posts) => posts.append(&mut batch_posts),
+            Err(err) => {
+                eprintln!("discourse: failed to fetch post batch: {}", err);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!(
+            "discourse: failed to fetch {} of {} missing-post batch(es) for {}; scan results would be incomplete",
+            failed,
+            batch_count,
+            topic_url
+        );
+    }
+
+    // `buffer_unordered` completes batches out of order; restore the
+    // forum's own post ordering so dedup in `process_posts` always keeps
+    // the earliest post for a given video, same as the old single-request
+    // response (which was already ordered).
+    posts.sort_by_key(|p| p.post_number);
+    Ok(posts)
+}
+
+async fn fetch_post_batch(cfg: &ScanConfig, topic_url: &str, post_ids: &[i64]) -> Result<Vec<Post>> {
+    let query: String = post_ids
+        .iter()
+        .map(|id| format!("post_ids[]={}", id))
+        .collect::<Vec<_>>()
+        .join("&");
+    let url_str = format!("{}/posts.json?{}", topic_url, query);
+    let url = Url::parse(&url_str).context("building posts.json url")?;
+    let resp = get_with_retries(cfg, &url).await?;
+    if !resp.status().is_success() {
+        anyhow::bail!("posts.json batch request returned status {}", resp.status());
+    }
+    let topic: Topic = resp.json().await?;
+    Ok(topic.post_stream.posts)
+}
+
 pub fn process_posts(
     posts: &[Post],
     topic_url: &str,
@@ -393,9 +1042,9 @@ pub fn process_posts(
                             v.insert(VideoEntry {
                                 video_id: video_id_clone,
                                 source_post_url: format!("{}/{}", topic_url, p.post_number),
+                                post_number: p.post_number,
                                 username: p.username.clone(),
-                                tip_unified_address: None,
-                                tip_has_transparent: None,
+                                ..Default::default()
                             });
                         }
                         Entry::Occupied(_) => {}
@@ -408,18 +1057,63 @@ pub fn process_posts(
 }
 
 pub async fn run(topic_url: &str, out_path: &str) -> Result<usize> {
+    run_with_config(topic_url, out_path, &ScanConfig::default()).await
+}
+
+/// Like [`run`], but `retain_unavailable` controls whether videos whose
+/// `availability` resolved to anything other than [`Availability::Available`]
+/// are kept in `videos.json` (tagged) or dropped from it entirely.
+pub async fn run_with_options(
+    topic_url: &str,
+    out_path: &str,
+    retain_unavailable: bool,
+) -> Result<usize> {
+    let cfg = ScanConfig::builder()
+        .retain_unavailable(retain_unavailable)
+        .build()?;
+    run_with_config(topic_url, out_path, &cfg).await
+}
+
+/// Like [`run`], but driven entirely by a caller-supplied [`ScanConfig`]
+/// (HTTP timeouts, TLS backend, retry policy, and `retain_unavailable`).
+pub async fn run_with_config(topic_url: &str, out_path: &str, cfg: &ScanConfig) -> Result<usize> {
+    let map = scan_videos(cfg, topic_url).await?;
+    let len = map.len();
+    let json = serde_json::to_string_pretty(&map)?;
+    fs::write(out_path, json)?;
+    eprintln!("Wrote {} unique videos to {}", len, out_path);
+    Ok(len)
+}
+
+/// Like [`run_with_config`], but additionally selects the output
+/// serialization via `format`. Requires the `rss` feature.
+#[cfg(feature = "rss")]
+pub async fn run_with_format(
+    topic_url: &str,
+    out_path: &str,
+    format: OutputFormat,
+    cfg: &ScanConfig,
+) -> Result<usize> {
+    let map = scan_videos(cfg, topic_url).await?;
+    let len = map.len();
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&map)?;
+            fs::write(out_path, json)?;
+        }
+        OutputFormat::Rss => {
+            let xml = render_rss_feed(topic_url, &map);
+            fs::write(out_path, xml)?;
+        }
+    }
+    eprintln!("Wrote {} unique videos to {}", len, out_path);
+    Ok(len)
+}
+
+async fn scan_videos(cfg: &ScanConfig, topic_url: &str) -> Result<HashMap<String, VideoEntry>> {
     let topic_url = topic_url.trim_end_matches('/');
     let thread_url = Url::parse(topic_url).context("invalid topic url")?;
-    let url = format!("{}.json?print=true", topic_url);
-    let resp = CLIENT.get(&url).send().await?;
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
-        eprintln!("DISCOURSE ERROR {} -> {}\n{}", url, status, body);
-        anyhow::bail!("GET {}", url);
-    }
-    let topic: Topic = resp.json().await?;
-    let posts = topic.post_stream.posts;
+    let posts = fetch_all_posts(cfg, topic_url).await?;
     let mut map = process_posts(&posts, topic_url, &CURATION_DENYLIST);
 
     let usernames: HashSet<String> = map
@@ -438,7 +1132,7 @@ pub async fn run(topic_url: &str, out_path: &str) -> Result<usize> {
         let profiles = stream::iter(usernames.into_iter().map(|username| {
             let base = thread_url.clone();
             async move {
-                let info = fetch_tip_info_with_cache(&base, &username).await;
+                let info = fetch_tip_info_with_cache(cfg, &base, &username).await;
                 (username, info)
             }
         }))
@@ -460,11 +1154,93 @@ pub async fn run(topic_url: &str, out_path: &str) -> Result<usize> {
         }
     }
 
-    let len = map.len();
-    let json = serde_json::to_string_pretty(&map)?;
-    fs::write(out_path, json)?;
-    eprintln!("Wrote {} unique videos to {}", len, out_path);
-    Ok(len)
+    let video_ids: Vec<String> = map.keys().cloned().collect();
+    if !video_ids.is_empty() {
+        let resolved = stream::iter(video_ids.into_iter().map(|video_id| async move {
+            let metadata = fetch_video_metadata_with_cache(cfg, &video_id).await;
+            (video_id, metadata)
+        }))
+        .buffer_unordered(PROFILE_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+        for (video_id, metadata) in resolved {
+            let Some(metadata) = metadata else {
+                continue;
+            };
+            if let Some(entry) = map.get_mut(&video_id) {
+                entry.title = metadata.title;
+                entry.channel_name = metadata.channel_name;
+                entry.channel_id = metadata.channel_id;
+                entry.duration_secs = metadata.duration_secs;
+                entry.thumbnail_url = metadata.thumbnail_url;
+                entry.availability = metadata.availability;
+                entry.scheduled_start = metadata.scheduled_start;
+            }
+        }
+    }
+
+    if !cfg.retain_unavailable {
+        map.retain(|_, entry| {
+            matches!(
+                entry.availability,
+                None | Some(Availability::Available)
+            )
+        });
+    }
+
+    Ok(map)
+}
+
+#[cfg(feature = "rss")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Rss,
+}
+
+#[cfg(feature = "rss")]
+fn render_rss_feed(topic_url: &str, map: &HashMap<String, VideoEntry>) -> String {
+    use quick_xml::escape::escape;
+
+    let mut entries: Vec<&VideoEntry> = map.values().collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.post_number));
+
+    let mut items = String::new();
+    for entry in entries {
+        let title = entry
+            .title
+            .clone()
+            .unwrap_or_else(|| entry.video_id.clone());
+        let link = format!("https://www.youtube.com/watch?v={}", entry.video_id);
+        items.push_str("    <item>\n");
+        items.push_str(&format!("      <title>{}</title>\n", escape(&title)));
+        items.push_str(&format!("      <link>{}</link>\n", escape(&link)));
+        items.push_str(&format!("      <guid>{}</guid>\n", escape(&entry.video_id)));
+        items.push_str(&format!(
+            "      <source url=\"{}\">{}</source>\n",
+            escape(&entry.source_post_url),
+            escape(&entry.source_post_url)
+        ));
+        if let Some(tip) = &entry.tip_unified_address {
+            items.push_str(&format!("      <zcash:tip>{}</zcash:tip>\n", escape(tip)));
+        }
+        items.push_str("    </item>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<rss version=\"2.0\" xmlns:zcash=\"https://github.com/aphelionz/zcash-radio\">\n\
+<channel>\n\
+<title>zcash-radio</title>\n\
+<link>{}</link>\n\
+<description>What are you listening to?</description>\n\
+{}\
+</channel>\n\
+</rss>\n",
+        escape(topic_url),
+        items
+    )
 }
 
 #[cfg(test)]
@@ -517,21 +1293,25 @@ mod tests {
     fn test_process_posts_dedup_and_denylist() {
         let posts = vec![
             Post {
+                id: 101,
                 post_number: 1,
                 cooked: "<a href=\"https://youtu.be/AAAAAAAAAAA\">one</a>".into(),
                 username: "alice".into(),
             },
             Post {
+                id: 102,
                 post_number: 2,
                 cooked: "<a href=\"https://www.youtube.com/watch?v=BBBBBBBBBBB\">two</a>".into(),
                 username: "bob".into(),
             },
             Post {
+                id: 103,
                 post_number: 3,
                 cooked: "<a href=\"https://youtu.be/BBBBBBBBBBB\">dup</a>".into(),
                 username: "carol".into(),
             },
             Post {
+                id: 104,
                 post_number: 4,
                 cooked: "<a href=\"https://example.com/video\">nope</a>".into(),
                 username: "dave".into(),
@@ -543,6 +1323,280 @@ mod tests {
         let entry = map.get("BBBBBBBBBBB").unwrap();
         assert_eq!(entry.source_post_url, "https://forum/2");
         assert_eq!(entry.username, "bob");
+        assert_eq!(entry.post_number, 2);
+    }
+
+    #[test]
+    fn test_parse_player_response_extracts_fields() {
+        let value = serde_json::json!({
+            "videoDetails": {
+                "title": "Some Video",
+                "author": "Some Channel",
+                "channelId": "UCxxxxxxxxxxxxxxxxxxxxxx",
+                "lengthSeconds": "123",
+                "thumbnail": {
+                    "thumbnails": [
+                        {"url": "https://example.com/small.jpg", "width": 120},
+                        {"url": "https://example.com/large.jpg", "width": 480}
+                    ]
+                }
+            }
+        });
+        let metadata = parse_player_response(&value).unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Some Video"));
+        assert_eq!(metadata.channel_name.as_deref(), Some("Some Channel"));
+        assert_eq!(metadata.duration_secs, Some(123));
+        assert_eq!(
+            metadata.thumbnail_url.as_deref(),
+            Some("https://example.com/large.jpg")
+        );
+    }
+
+    #[test]
+    fn test_parse_player_response_handles_missing_video_details() {
+        // A deleted/ERROR video's InnerTube response has no `videoDetails`
+        // at all — this must not be treated as a parse failure.
+        let value = serde_json::json!({
+            "playabilityStatus": {
+                "status": "ERROR",
+                "reason": "Video unavailable"
+            }
+        });
+        let metadata = parse_player_response(&value).unwrap();
+        assert_eq!(metadata.availability, Some(Availability::Unavailable));
+        assert_eq!(metadata.title, None);
+    }
+
+    #[test]
+    fn test_parse_availability_maps_statuses() {
+        assert_eq!(
+            parse_availability(&serde_json::json!({"status": "OK"})),
+            Availability::Available
+        );
+        assert_eq!(
+            parse_availability(&serde_json::json!({"status": "UNPLAYABLE"})),
+            Availability::Unavailable
+        );
+        assert_eq!(
+            parse_availability(&serde_json::json!({"status": "LOGIN_REQUIRED"})),
+            Availability::Private
+        );
+        assert_eq!(
+            parse_availability(
+                &serde_json::json!({"status": "LOGIN_REQUIRED", "reason": "Age-restricted video"})
+            ),
+            Availability::AgeRestricted
+        );
+        assert_eq!(
+            parse_availability(&serde_json::json!({"status": "LIVE_STREAM_OFFLINE"})),
+            Availability::Upcoming
+        );
+    }
+
+    #[test]
+    fn test_find_scheduled_start_in_live_streamability_renderer() {
+        let value = serde_json::json!({
+            "playabilityStatus": {
+                "status": "LIVE_STREAM_OFFLINE",
+                "liveStreamability": {
+                    "liveStreamabilityRenderer": {
+                        "offlineSlate": {
+                            "liveStreamOfflineSlateRenderer": {
+                                "scheduledStartTime": "1234567890"
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        assert_eq!(find_scheduled_start(&value), Some(1234567890));
+    }
+
+    #[cfg(feature = "rss")]
+    #[test]
+    fn test_render_rss_feed_orders_by_post_number_desc() {
+        let mut map = HashMap::new();
+        map.insert(
+            "AAAAAAAAAAA".to_string(),
+            VideoEntry {
+                video_id: "AAAAAAAAAAA".into(),
+                source_post_url: "https://forum/1".into(),
+                post_number: 1,
+                title: Some("First".into()),
+                ..Default::default()
+            },
+        );
+        map.insert(
+            "BBBBBBBBBBB".to_string(),
+            VideoEntry {
+                video_id: "BBBBBBBBBBB".into(),
+                source_post_url: "https://forum/2".into(),
+                post_number: 2,
+                tip_unified_address: Some("u1abc".into()),
+                ..Default::default()
+            },
+        );
+        let xml = render_rss_feed("https://forum/topic", &map);
+        let first = xml.find("BBBBBBBBBBB").unwrap();
+        let second = xml.find("AAAAAAAAAAA").unwrap();
+        assert!(first < second);
+        assert!(xml.contains("<zcash:tip>u1abc</zcash:tip>"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_posts_paginates_missing_posts() {
+        let server = httpmock::MockServer::start();
+        let topic_json = serde_json::json!({
+            "post_stream": {
+                "posts": [{
+                    "id": 1,
+                    "post_number": 1,
+                    "cooked": "<a href=\"https://youtu.be/AAAAAAAAAAA\">v</a>",
+                    "username": "alice"
+                }],
+                "stream": [1, 2]
+            }
+        });
+        server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/topic.json");
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body_obj(&topic_json);
+        });
+
+        let batch_json = serde_json::json!({
+            "post_stream": {
+                "posts": [{
+                    "id": 2,
+                    "post_number": 2,
+                    "cooked": "<a href=\"https://youtu.be/BBBBBBBBBBB\">v</a>",
+                    "username": "bob"
+                }]
+            }
+        });
+        server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/topic/posts.json")
+                .query_param("post_ids[]", "2");
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body_obj(&batch_json);
+        });
+
+        let url = format!("{}/topic", server.base_url());
+        let cfg = ScanConfig::default();
+        let posts = fetch_all_posts(&cfg, &url).await.unwrap();
+        assert_eq!(posts.len(), 2);
+        assert!(posts.iter().any(|p| p.id == 1));
+        assert!(posts.iter().any(|p| p.id == 2));
+        assert!(posts.windows(2).all(|w| w[0].post_number <= w[1].post_number));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_posts_fails_loudly_on_batch_error() {
+        let server = httpmock::MockServer::start();
+        let topic_json = serde_json::json!({
+            "post_stream": {
+                "posts": [{
+                    "id": 1,
+                    "post_number": 1,
+                    "cooked": "<a href=\"https://youtu.be/AAAAAAAAAAA\">v</a>",
+                    "username": "alice"
+                }],
+                "stream": [1, 2]
+            }
+        });
+        server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/topic.json");
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body_obj(&topic_json);
+        });
+        // No mock registered for the posts.json batch request, so it 404s
+        // and the missing post can never be fetched.
+
+        let url = format!("{}/topic", server.base_url());
+        let cfg = ScanConfig::builder().retry_attempts(1).build().unwrap();
+        let result = fetch_all_posts(&cfg, &url).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_invidious_response_extracts_fields() {
+        let value = serde_json::json!({
+            "title": "Some Video",
+            "author": "Some Channel",
+            "authorId": "UCxxxxxxxxxxxxxxxxxxxxxx",
+            "lengthSeconds": 123,
+            "videoThumbnails": [
+                {"url": "https://example.com/small.jpg", "width": 120},
+                {"url": "https://example.com/large.jpg", "width": 480}
+            ]
+        });
+        let metadata = parse_invidious_response(&value).unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Some Video"));
+        assert_eq!(metadata.duration_secs, Some(123));
+        assert_eq!(metadata.availability, Some(Availability::Available));
+        assert_eq!(
+            metadata.thumbnail_url.as_deref(),
+            Some("https://example.com/large.jpg")
+        );
+    }
+
+    #[test]
+    fn test_merge_invidious_supplement_keeps_innertube_availability() {
+        let base = VideoMetadata {
+            availability: Some(Availability::Private),
+            scheduled_start: None,
+            ..Default::default()
+        };
+        let supplement = VideoMetadata {
+            title: Some("Mirrored Title".into()),
+            availability: Some(Availability::Available),
+            ..Default::default()
+        };
+        let merged = merge_invidious_supplement(base, Some(supplement));
+        assert_eq!(merged.title.as_deref(), Some("Mirrored Title"));
+        assert_eq!(merged.availability, Some(Availability::Private));
+    }
+
+    #[test]
+    fn test_invidious_instance_order_prefers_last_good() {
+        let instances = [
+            "https://a.example".to_string(),
+            "https://b.example".to_string(),
+            "https://c.example".to_string(),
+        ];
+        remember_invidious_instance("https://c.example");
+        let order = invidious_instance_order(&instances);
+        assert_eq!(order[0], "https://c.example");
+        assert_eq!(order.len(), 3);
+    }
+
+    #[test]
+    fn test_retry_delay_is_bounded_full_jitter() {
+        let cfg = ScanConfig::builder()
+            .retry_base_delay_ms(100)
+            .build()
+            .unwrap();
+        for attempt in 0..4 {
+            let delay = retry_delay(&cfg, attempt);
+            let upper = Duration::from_millis(100 * (1u64 << attempt));
+            assert!(delay <= upper);
+        }
+    }
+
+    #[test]
+    fn test_scan_config_builder_overrides_defaults() {
+        let cfg = ScanConfig::builder()
+            .retry_attempts(7)
+            .retain_unavailable(false)
+            .invidious_instances(vec!["https://custom.example".to_string()])
+            .build()
+            .unwrap();
+        assert_eq!(cfg.retry_attempts, 7);
+        assert!(!cfg.retain_unavailable);
+        assert_eq!(cfg.invidious_instances, vec!["https://custom.example"]);
     }
 
     #[test]
@@ -557,16 +1611,16 @@ mod tests {
         let topic_json = serde_json::json!({
             "post_stream": {
                 "posts": [{
+                    "id": 1,
                     "post_number": 1,
                     "cooked": "<a href=\"https://youtu.be/BBBBBBBBBBB\">v</a>",
                     "username": "alice"
-                }]
+                }],
+                "stream": [1]
             }
         });
         server.mock(|when, then| {
-            when.method(httpmock::Method::GET)
-                .path("/topic.json")
-                .query_param("print", "true");
+            when.method(httpmock::Method::GET).path("/topic.json");
             then.status(200)
                 .header("content-type", "application/json")
                 .json_body_obj(&topic_json);